@@ -0,0 +1,36 @@
+/*
+ * error.rs
+ * Error types shared across the lexing, parsing and evaluation stages.
+ */
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcError {
+    Math(String),
+    Syntax(String),
+    Parser(String),
+    Div0,
+    Help,
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalcError::Math(m) => write!(f, "Math error: {}", m),
+            CalcError::Syntax(s) => write!(f, "Syntax error: {}", s),
+            CalcError::Parser(p) => write!(f, "Parser error: {}", p),
+            CalcError::Div0 => write!(f, "Division by zero!"),
+            CalcError::Help => write!(f, "{}", HELP_TEXT),
+        }
+    }
+}
+
+const HELP_TEXT: &str = "eva - an easy to use calculator REPL\n\
+    supports +, -, *, /, ^, %, parentheses and a handful of functions\n\
+    (sin, cos, tan, asin, acos, atan, deg, rad, round, exp, exp2, log, log10, sqrt, nroot)";
+
+/// Turns an error coming out of the eval pipeline into the text shown to the user.
+pub fn handler(err: CalcError) -> String {
+    format!("{}", err)
+}
@@ -0,0 +1,162 @@
+/*
+ * fixed.rs
+ * Scaled-integer fixed-point arithmetic, used by `--numbers fixed --decimals
+ * N` mode so monetary/tabular calculations stay reproducible and free of
+ * binary-float artifacts.
+ */
+
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+
+use crate::error::CalcError;
+
+/// A value `v * 10^-decimals`, e.g. `FixedPoint { v: 1050, decimals: 2 }` is `10.50`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedPoint {
+    pub v: BigInt,
+    pub decimals: usize,
+}
+
+impl FixedPoint {
+    pub fn new(v: BigInt, decimals: usize) -> FixedPoint {
+        FixedPoint { v, decimals }
+    }
+
+    pub fn from_literal(text: &str, decimals: usize) -> FixedPoint {
+        let (int_part, frac_part) = match text.find('.') {
+            Some(dot) => (&text[..dot], &text[dot + 1..]),
+            None => (text, ""),
+        };
+        let mut frac = frac_part.to_string();
+        if frac.len() > decimals {
+            frac.truncate(decimals);
+        } else {
+            frac.push_str(&"0".repeat(decimals - frac.len()));
+        }
+        let digits = format!("{}{}", int_part, frac);
+        let v: BigInt = digits.parse().unwrap_or_else(|_| BigInt::zero());
+        FixedPoint::new(v, decimals)
+    }
+
+    fn scale(&self) -> BigInt {
+        BigInt::from(10).pow(self.decimals as u32)
+    }
+
+    /// Returns both scaled integers at the larger of the two `decimals`,
+    /// rescaling whichever side has fewer, so an op can combine them.
+    fn aligned(&self, other: &FixedPoint) -> (BigInt, BigInt) {
+        if self.decimals == other.decimals {
+            (self.v.clone(), other.v.clone())
+        } else if self.decimals > other.decimals {
+            let factor = BigInt::from(10).pow((self.decimals - other.decimals) as u32);
+            (self.v.clone(), &other.v * factor)
+        } else {
+            let factor = BigInt::from(10).pow((other.decimals - self.decimals) as u32);
+            (&self.v * factor, other.v.clone())
+        }
+    }
+
+    pub fn add(&self, other: &FixedPoint) -> FixedPoint {
+        let decimals = self.decimals.max(other.decimals);
+        let (a, b) = self.aligned(other);
+        FixedPoint::new(a + b, decimals)
+    }
+
+    pub fn sub(&self, other: &FixedPoint) -> FixedPoint {
+        let decimals = self.decimals.max(other.decimals);
+        let (a, b) = self.aligned(other);
+        FixedPoint::new(a - b, decimals)
+    }
+
+    pub fn mul(&self, other: &FixedPoint) -> FixedPoint {
+        let decimals = self.decimals.max(other.decimals);
+        let (a, b) = self.aligned(other);
+        FixedPoint::new((a * b) / self.scale_n(decimals), decimals)
+    }
+
+    pub fn div(&self, other: &FixedPoint) -> Result<FixedPoint, CalcError> {
+        if other.v.is_zero() {
+            return Err(CalcError::Div0);
+        }
+        let decimals = self.decimals.max(other.decimals);
+        let (a, b) = self.aligned(other);
+        Ok(FixedPoint::new((a * self.scale_n(decimals)) / b, decimals))
+    }
+
+    pub fn neg(&self) -> FixedPoint {
+        FixedPoint::new(-self.v.clone(), self.decimals)
+    }
+
+    /// Whether this value has no fractional part, e.g. as the exponent of `^`.
+    pub fn is_integer(&self) -> bool {
+        (&self.v % self.scale()).is_zero()
+    }
+
+    fn scale_n(&self, decimals: usize) -> BigInt {
+        BigInt::from(10).pow(decimals as u32)
+    }
+
+    /// Integer exponentiation; negative exponents divide instead of panicking.
+    pub fn pow(&self, exp: i64) -> Result<FixedPoint, CalcError> {
+        if exp == 0 {
+            return Ok(FixedPoint::new(self.scale(), self.decimals));
+        }
+        if exp > 0 {
+            let mut result = self.clone();
+            for _ in 1..exp {
+                result = result.mul(self);
+            }
+            Ok(result)
+        } else {
+            if self.v.is_zero() {
+                return Err(CalcError::Div0);
+            }
+            let one = FixedPoint::new(self.scale(), self.decimals);
+            let mut result = self.clone();
+            for _ in 1..(-exp) {
+                result = result.mul(self);
+            }
+            one.div(&result)
+        }
+    }
+
+    /// Rounds to `dps` decimal places, half-away-from-zero. A no-op when
+    /// `dps >= decimals` since there's nothing to discard.
+    pub fn round_mut(&mut self, dps: usize) {
+        if dps >= self.decimals {
+            return;
+        }
+        let factor = BigInt::from(10).pow((self.decimals - dps) as u32);
+        let half = &factor / BigInt::from(2);
+        let sign = if self.v.is_negative() {
+            -BigInt::from(1)
+        } else {
+            BigInt::from(1)
+        };
+        self.v = ((&self.v + &sign * &half) / &factor) * &factor;
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let v: f64 = self.v.to_string().parse().unwrap_or(f64::NAN);
+        v / 10f64.powi(self.decimals as i32)
+    }
+
+    pub fn to_display_string(&self, fix: usize) -> String {
+        let mut rounded = self.clone();
+        if fix < rounded.decimals {
+            rounded.round_mut(fix);
+        }
+        let digits = rounded.v.abs().to_string();
+        let sign = if rounded.v.is_negative() { "-" } else { "" };
+        if rounded.decimals == 0 {
+            return format!("{}{}", sign, digits);
+        }
+        let digits = if digits.len() <= rounded.decimals {
+            "0".repeat(rounded.decimals - digits.len() + 1) + &digits
+        } else {
+            digits
+        };
+        let (int_part, frac_part) = digits.split_at(digits.len() - rounded.decimals);
+        format!("{}{}.{}", sign, int_part, frac_part)
+    }
+}
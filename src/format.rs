@@ -0,0 +1,72 @@
+/*
+ * format.rs
+ * Input normalisation and output formatting.
+ */
+
+use crate::error::CalcError;
+use crate::rational::Value;
+use crate::Configuration;
+
+/// Appends missing closing parentheses so a trailing `)` isn't required,
+/// e.g. `sin(30` becomes `sin(30)`. Extra, unmatched `)` is a hard error.
+pub fn autobalance_parens(input: &str) -> Result<String, CalcError> {
+    let opens = input.matches('(').count();
+    let closes = input.matches(')').count();
+    if closes > opens {
+        return Err(CalcError::Syntax("Mismatched parentheses!".to_string()));
+    }
+    let mut balanced = input.to_string();
+    balanced.push_str(&")".repeat(opens - closes));
+    Ok(balanced)
+}
+
+/// Prints `output`. Base 10 renders straight from the `Value`
+/// (`Rational`/`FixedPoint` keep their own precision) at `config.fix` decimal
+/// places with trailing zeros dropped, so `--numbers exact`/`fixed` don't
+/// lose digits round-tripping through f64. Any other base goes through f64
+/// and `to_base`, which prints the nearest integer; `config.fix` is ignored.
+pub fn pprint(output: &Value, config: &Configuration) {
+    if config.base == 10 {
+        let rendered = match output {
+            Value::Exact(r) => r.to_fixed_string(config.fix),
+            Value::Fixed(d) => d.to_display_string(config.fix),
+            Value::Inexact(f) => format!("{:.*}", config.fix, f),
+        };
+        println!("{}", trim_trailing_zeros(&rendered));
+    } else {
+        println!("{}", to_base(output.to_f64(), config.base));
+    }
+}
+
+fn trim_trailing_zeros(s: &str) -> String {
+    if !s.contains('.') {
+        return s.to_string();
+    }
+    let trimmed = s.trim_end_matches('0');
+    trimmed.trim_end_matches('.').to_string()
+}
+
+fn to_base(value: f64, base: usize) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    // A base below 2 can't make progress in the digit loop below (`n % 1 ==
+    // 0` forever), and one above the DIGITS table's length would index out
+    // of bounds; fall back to decimal rather than hang or panic.
+    let base = if (2..=DIGITS.len()).contains(&base) { base } else { 10 };
+    let negative = value < 0.;
+    let mut n = value.abs().round() as u64;
+    if n == 0 {
+        return "0".to_string();
+    }
+    let mut digits = Vec::new();
+    while n > 0 {
+        digits.push(DIGITS[(n % base as u64) as usize]);
+        n /= base as u64;
+    }
+    digits.reverse();
+    let rendered = String::from_utf8(digits).unwrap();
+    if negative {
+        format!("-{}", rendered)
+    } else {
+        rendered
+    }
+}
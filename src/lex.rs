@@ -0,0 +1,280 @@
+/*
+ * lex.rs
+ * Turns an input string into a stream of tokens ready for the shunting-yard
+ * pass in parse.rs.
+ */
+
+use crate::error::CalcError;
+use crate::fixed::FixedPoint;
+use crate::rational::{Rational, Value};
+use crate::symbols::Symbols;
+use crate::{Configuration, NumberMode};
+
+#[derive(Debug, Clone)]
+pub enum Token {
+    Num(Value),
+    Plus,
+    Minus,
+    /// Unary negation, e.g. the `-` in `e^-7` or `-x`. Distinct from the
+    /// binary `Minus` so it can bind tighter than `^` and take a single
+    /// operand at eval time instead of requiring a preceding `Num(-1) *`.
+    Negate,
+    Multiply,
+    Divide,
+    Modulo,
+    Caret,
+    LParen,
+    RParen,
+    Comma,
+    Function(String),
+    /// A name that resolves to a value at eval time: a function parameter
+    /// (bound when the call runs) or a user variable looked up by name.
+    Var(String),
+    UserFunction(String),
+}
+
+const FUNCTIONS_1_ARG: &[&str] = &[
+    "sin", "cos", "tan", "asin", "acos", "atan", "deg", "rad", "round", "exp", "exp2", "log10",
+    "sqrt",
+];
+const FUNCTIONS_2_ARG: &[&str] = &["log", "nroot"];
+
+pub fn is_function(name: &str) -> bool {
+    FUNCTIONS_1_ARG.contains(&name) || FUNCTIONS_2_ARG.contains(&name)
+}
+
+pub fn arity(name: &str) -> usize {
+    if FUNCTIONS_2_ARG.contains(&name) {
+        2
+    } else {
+        1
+    }
+}
+
+pub fn lexer(
+    input: &str,
+    history: &[f64],
+    symbols: &Symbols,
+    config: &Configuration,
+) -> Result<Vec<Token>, CalcError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '0'..='9' | '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                push_implicit_multiply(&mut tokens);
+                tokens.push(Token::Num(number_literal(&literal, config)));
+            }
+            '_' => {
+                push_implicit_multiply(&mut tokens);
+                i += 1;
+                let num_start = i;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let n: usize = if i > num_start {
+                    chars[num_start..i].iter().collect::<String>().parse().unwrap()
+                } else {
+                    1
+                };
+                tokens.push(Token::Num(Value::Inexact(recall(history, n)?)));
+            }
+            '+' => {
+                tokens.push(Token::Plus);
+                i += 1;
+            }
+            '-' => {
+                if is_unary_position(&tokens) {
+                    tokens.push(Token::Negate);
+                } else {
+                    tokens.push(Token::Minus);
+                }
+                i += 1;
+            }
+            '*' => {
+                i += 1;
+                if i < chars.len() && chars[i] == '*' {
+                    tokens.push(Token::Caret);
+                    i += 1;
+                } else {
+                    tokens.push(Token::Multiply);
+                }
+            }
+            '/' => {
+                tokens.push(Token::Divide);
+                i += 1;
+            }
+            '%' => {
+                tokens.push(Token::Modulo);
+                i += 1;
+            }
+            '^' => {
+                tokens.push(Token::Caret);
+                i += 1;
+            }
+            '(' => {
+                push_implicit_multiply(&mut tokens);
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            c if c.is_alphabetic() => {
+                let start = i;
+                while i < chars.len() && chars[i].is_alphabetic() {
+                    i += 1;
+                }
+                // Function names like `exp2`/`log10` carry digits; extend the
+                // run into any immediately-following digits when doing so
+                // spells a known function, so they aren't mistaken for an
+                // implicit multiply (`exp2(8)` vs. `exp` `2` `(8)`).
+                let mut end = i;
+                while end < chars.len() && chars[end].is_ascii_digit() {
+                    end += 1;
+                }
+                if end > i {
+                    let candidate: String = chars[start..end].iter().collect();
+                    if is_function(&candidate) {
+                        i = end;
+                    }
+                }
+                let ident: String = chars[start..i].iter().collect();
+                if ident == "ans" && chars.get(i) == Some(&'(') {
+                    push_implicit_multiply(&mut tokens);
+                    i += 1;
+                    let num_start = i;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                    if i == num_start {
+                        return Err(CalcError::Syntax("Expected a number in ans(n)".to_string()));
+                    }
+                    let n: usize = chars[num_start..i].iter().collect::<String>().parse().unwrap();
+                    if chars.get(i) != Some(&')') {
+                        return Err(CalcError::Syntax("Mismatched parentheses!".to_string()));
+                    }
+                    i += 1;
+                    tokens.push(Token::Num(Value::Inexact(recall(history, n)?)));
+                } else {
+                    push_implicit_multiply(&mut tokens);
+                    lex_ident(&ident, &chars, &mut i, &mut tokens, symbols, config)?;
+                }
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+            }
+            _ => return Err(CalcError::Syntax(format!("Unexpected character '{}'", c))),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Looks up the nth-prior answer in `history` (`history[0]` is the most
+/// recent), where `n = 1` is `_`/`ans(1)`, `n = 2` is `_2`/`ans(2)`, and so on.
+fn recall(history: &[f64], n: usize) -> Result<f64, CalcError> {
+    n.checked_sub(1)
+        .and_then(|idx| history.get(idx))
+        .copied()
+        .ok_or_else(|| CalcError::Math("no previous answer to recall".to_string()))
+}
+
+fn number_literal(literal: &str, config: &Configuration) -> Value {
+    match config.numbers {
+        NumberMode::Exact => Value::Exact(Rational::from_literal(literal)),
+        NumberMode::Fixed => Value::Fixed(FixedPoint::from_literal(literal, config.decimals)),
+        NumberMode::Float => Value::Inexact(literal.parse().unwrap_or(0.)),
+    }
+}
+
+fn is_unary_position(tokens: &[Token]) -> bool {
+    matches!(
+        tokens.last(),
+        None | Some(Token::LParen)
+            | Some(Token::Comma)
+            | Some(Token::Plus)
+            | Some(Token::Minus)
+            | Some(Token::Negate)
+            | Some(Token::Multiply)
+            | Some(Token::Divide)
+            | Some(Token::Modulo)
+            | Some(Token::Caret)
+    )
+}
+
+fn push_implicit_multiply(tokens: &mut Vec<Token>) {
+    match tokens.last() {
+        Some(Token::Num(_)) | Some(Token::RParen) | Some(Token::Var(_)) => {
+            tokens.push(Token::Multiply)
+        }
+        _ => {}
+    }
+}
+
+/// Resolves an alphabetic run into a built-in or user function call, a user
+/// variable's value, a constant (optionally followed by a digit run meaning
+/// implicit multiplication, e.g. `e2`), or, if it's none of the above, a
+/// `Token::Var` left for eval time to resolve.
+fn lex_ident(
+    ident: &str,
+    chars: &[char],
+    i: &mut usize,
+    tokens: &mut Vec<Token>,
+    symbols: &Symbols,
+    config: &Configuration,
+) -> Result<(), CalcError> {
+    if is_function(ident) {
+        tokens.push(Token::Function(ident.to_string()));
+        return Ok(());
+    }
+    if symbols.funcs.contains_key(ident) {
+        tokens.push(Token::UserFunction(ident.to_string()));
+        return Ok(());
+    }
+    if let Some(value) = symbols.vars.get(ident) {
+        tokens.push(Token::Num(Value::Inexact(*value)));
+        return Ok(());
+    }
+    let (const_name, const_value) = if ident == "pi" || ident.starts_with("pi") {
+        ("pi", std::f64::consts::PI)
+    } else if ident.starts_with('e') {
+        ("e", std::f64::consts::E)
+    } else {
+        // Not a known function/constant/variable: treat it as an unresolved
+        // name. Valid only inside a function body being defined, where it
+        // stands for one of the function's parameters; eval_postfix errors
+        // if it's never bound.
+        tokens.push(Token::Var(ident.to_string()));
+        return Ok(());
+    };
+    if ident != const_name {
+        tokens.push(Token::Var(ident.to_string()));
+        return Ok(());
+    }
+    tokens.push(Token::Num(Value::Inexact(const_value)));
+
+    // Trailing digits right after a constant, e.g. `e2`, mean `e * 2`.
+    if *i < chars.len() && (chars[*i].is_ascii_digit() || chars[*i] == '.') {
+        let start = *i;
+        while *i < chars.len() && (chars[*i].is_ascii_digit() || chars[*i] == '.') {
+            *i += 1;
+        }
+        let literal: String = chars[start..*i].iter().collect();
+        tokens.push(Token::Multiply);
+        tokens.push(Token::Num(number_literal(&literal, config)));
+    }
+    Ok(())
+}
@@ -11,52 +11,73 @@ use std::path::PathBuf;
 
 // modules
 mod error;
+mod fixed;
 mod format;
 mod lex;
 mod parse;
+mod rational;
 mod readline;
+mod symbols;
 use crate::error::{handler, CalcError};
 use crate::format::*;
 use crate::lex::*;
 use crate::parse::*;
+use crate::rational::Value;
 use crate::readline::*;
+use crate::symbols::Symbols;
 
 // extern crates
 use clap::{App, AppSettings, Arg};
 use directories::{ProjectDirs, UserDirs};
-use lazy_static::lazy_static;
 use rustyline::error::ReadlineError;
 
 /* end of imports */
 
-struct Configuration {
-    radian_mode: bool,
-    fix: usize,
-    base: usize,
-    input: String,
+/// Which value type flows through the eval pipeline; see `Value` in
+/// rational.rs for the representations themselves.
+#[derive(PartialEq, Default)]
+pub enum NumberMode {
+    #[default]
+    Float,
+    Exact,
+    Fixed,
 }
 
-#[cfg(not(test))]
-lazy_static! {
-    static ref CONFIGURATION: Configuration = parse_arguments();
+/// Runtime settings, seeded from the CLI flags and then mutable for the
+/// lifetime of the REPL via `:base`/`:fix`/`:radian`/`:mode` meta-commands.
+pub struct Configuration {
+    pub radian_mode: bool,
+    pub fix: usize,
+    pub base: usize,
+    pub numbers: NumberMode,
+    pub decimals: usize,
+    pub history_length: usize,
+    pub input: String,
 }
 
 #[cfg(test)]
-lazy_static! {
-    static ref CONFIGURATION: Configuration = Configuration {
-        radian_mode: false,
-        fix: 10,
-        base: 10,
-        input: "".to_string(),
-    };
+impl Default for Configuration {
+    fn default() -> Configuration {
+        Configuration {
+            radian_mode: false,
+            fix: 10,
+            base: 10,
+            numbers: NumberMode::Float,
+            decimals: 10,
+            history_length: 100,
+            input: "".to_string(),
+        }
+    }
 }
 
 fn main() {
-    if !CONFIGURATION.input.is_empty() {
+    let mut config = parse_arguments();
+    if !config.input.is_empty() {
         // command mode //
-        let evaled = eval_math_expression(&CONFIGURATION.input[..], Some(0.));
+        let symbols = Symbols::new();
+        let evaled = eval_math_value(&config.input.clone()[..], &[0.], &symbols, &config);
         match evaled {
-            Ok(ans) => pprint(ans),
+            Ok(ans) => pprint(&ans, &config),
             Err(e) => {
                 eprintln!("{}", handler(e));
                 std::process::exit(1);
@@ -67,64 +88,55 @@ fn main() {
         // create fancy readline
         let mut rl = create_readline();
 
-        // previous answer
-        let mut prev_ans = None;
-
         // handle history storage
         let eva_dirs = ProjectDirs::from("com", "NerdyPepper", "eva").unwrap();
         let eva_data_dir = eva_dirs.data_dir();
         let eva_cache_dir = eva_dirs.cache_dir();
         let mut history_path = PathBuf::from(eva_data_dir);
         let mut previous_ans_path = PathBuf::from(eva_cache_dir);
+        let mut definitions_path = PathBuf::from(eva_data_dir);
 
-        if let Err(_) = create_dir_all(eva_data_dir) {
+        if create_dir_all(eva_data_dir).is_err() {
             history_path = PathBuf::from(UserDirs::new().unwrap().home_dir());
+            definitions_path = PathBuf::from(UserDirs::new().unwrap().home_dir());
         }
-        if let Err(_) = create_dir_all(eva_cache_dir) {
+        if create_dir_all(eva_cache_dir).is_err() {
             previous_ans_path = PathBuf::from(UserDirs::new().unwrap().home_dir());
         }
         history_path.push("history.txt");
         previous_ans_path.push("previous_ans.txt");
-
-        if let Err(err) = std::fs::write(&previous_ans_path, "0") {
-            println!("Could not write to previous_ans_path");
-            println!("{:?}", err);
-            std::process::exit(1);
-        }
+        definitions_path.push("definitions.txt");
 
         if rl.load_history(history_path.as_path()).is_err() {
             println!("No previous history.")
         };
 
+        let mut symbols = Symbols::load(&definitions_path, &config);
+        let mut answers = load_answers(&previous_ans_path, config.history_length);
+
         // repl loop begins here
         loop {
             let readline = rl.readline("> ");
             match readline {
                 Ok(line) => {
                     rl.add_history_entry(line.as_str());
-                    let evaled = eval_math_expression(&line[..], prev_ans);
+                    if let Some(command) = line.strip_prefix(':') {
+                        handle_meta_command(command.trim(), &mut config);
+                        continue;
+                    }
+                    if let Some(target) = parse_binding(&line) {
+                        handle_binding(target, &answers, &mut symbols, &definitions_path, &config);
+                        continue;
+                    }
+                    let evaled = eval_math_value(&line[..], &answers, &symbols, &config);
                     match evaled {
                         Ok(ans) => {
-                            use std::fs::OpenOptions;
-                            use std::io::Write;
-                            prev_ans = Some(ans);
-                            pprint(ans);
-                            match OpenOptions::new()
-                                .write(true)
-                                .create(true)
-                                .open(&previous_ans_path)
-                            {
-                                Ok(mut file) => {
-                                    if let Err(err) = writeln!(file, "{}", ans) {
-                                        println!(
-                                            "Error while writing previous answer to file: {}",
-                                            err
-                                        )
-                                    }
-                                }
-                                Err(err) => {
-                                    println!("Error while writing previous answer to file: {}", err)
-                                }
+                            pprint(&ans, &config);
+                            let ans = round_to_f64(&ans, &config);
+                            answers.insert(0, ans);
+                            answers.truncate(config.history_length);
+                            if let Err(err) = append_answer(&previous_ans_path, ans) {
+                                println!("Error while writing previous answer to file: {}", err)
                             }
                         }
                         Err(e) => println!("{}", handler(e)),
@@ -145,6 +157,134 @@ fn main() {
     }
 }
 
+/// Reads the saved answer history back into memory, most recent first, so
+/// `_`/`_N`/`ans(n)` can resolve across sessions. Caps the in-memory vector
+/// to `history_length`, though the file itself is never trimmed.
+fn load_answers(path: &std::path::Path, history_length: usize) -> Vec<f64> {
+    let contents = std::fs::read_to_string(path).unwrap_or_default();
+    let mut answers: Vec<f64> = contents
+        .lines()
+        .filter_map(|line| line.parse().ok())
+        .collect();
+    answers.reverse();
+    answers.truncate(history_length);
+    answers
+}
+
+/// Appends one answer to the history file rather than overwriting it, so
+/// prior sessions' answers stay recallable.
+fn append_answer(path: &std::path::Path, ans: f64) -> std::io::Result<()> {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+    writeln!(file, "{}", ans)
+}
+
+/// The left-hand side of a top-level `=`: either a bare variable name
+/// (`x = 3 + 4`) or a function head (`f(a, b) = a^2 + b`).
+enum BindingTarget {
+    Variable(String),
+    Function(String, Vec<String>),
+}
+
+/// Recognises `name = ...` and `name(params) = ...`, returning the binding
+/// target and leaving the right-hand side for the caller to evaluate/lex.
+/// Returns `None` for anything else, so it's evaluated as a normal expression.
+fn parse_binding(line: &str) -> Option<(BindingTarget, String)> {
+    let eq = line.find('=')?;
+    let (lhs, rhs) = (line[..eq].trim(), line[eq + 1..].trim());
+
+    if let Some(open) = lhs.find('(') {
+        if !lhs.ends_with(')') {
+            return None;
+        }
+        let name = lhs[..open].trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_alphabetic()) {
+            return None;
+        }
+        let params: Vec<String> = lhs[open + 1..lhs.len() - 1]
+            .split(',')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        return Some((BindingTarget::Function(name.to_string(), params), rhs.to_string()));
+    }
+
+    if !lhs.is_empty() && lhs.chars().all(|c| c.is_alphabetic()) {
+        return Some((BindingTarget::Variable(lhs.to_string()), rhs.to_string()));
+    }
+    None
+}
+
+fn handle_binding(
+    target: (BindingTarget, String),
+    history: &[f64],
+    symbols: &mut Symbols,
+    definitions_path: &std::path::Path,
+    config: &Configuration,
+) {
+    let (target, rhs) = target;
+    match target {
+        BindingTarget::Variable(name) => {
+            match eval_math_expression(&rhs, history, symbols, config) {
+                Ok(value) => {
+                    symbols.vars.insert(name.clone(), value);
+                    println!("{} = {}", name, value);
+                    symbols.save(definitions_path);
+                }
+                Err(e) => println!("{}", handler(e)),
+            }
+        }
+        BindingTarget::Function(name, params) => {
+            match symbols.define_function(&name, params, &rhs, config) {
+                Ok(()) => {
+                    println!("{}(...) defined", name);
+                    symbols.save(definitions_path);
+                }
+                Err(e) => println!("{}", handler(e)),
+            }
+        }
+    }
+}
+
+/// Handles a `:`-prefixed meta-command (`:base 16`, `:fix 4`, `:radian on`,
+/// `:mode`), mutating the REPL's live `Configuration` in place. Unknown
+/// commands or malformed arguments print a message rather than erroring out,
+/// mirroring how a bad expression just prints and the REPL keeps going.
+fn handle_meta_command(command: &str, config: &mut Configuration) {
+    let mut parts = command.split_whitespace();
+    match parts.next() {
+        Some("base") => match parts.next().and_then(|v| v.parse().ok()) {
+            Some(base) if (2..=36).contains(&base) => config.base = base,
+            _ => println!("Usage: :base <RADIX> (2 - 36)"),
+        },
+        Some("fix") => match parts.next().and_then(|v| v.parse().ok()) {
+            Some(fix) => config.fix = fix,
+            None => println!("Usage: :fix <DECIMAL_PLACES>"),
+        },
+        Some("radian") => match parts.next() {
+            Some("on") => config.radian_mode = true,
+            Some("off") => config.radian_mode = false,
+            _ => println!("Usage: :radian on|off"),
+        },
+        Some("mode") => {
+            println!("base: {}", config.base);
+            println!("fix: {}", config.fix);
+            println!("radian: {}", config.radian_mode);
+            println!(
+                "numbers: {}",
+                match config.numbers {
+                    NumberMode::Float => "float",
+                    NumberMode::Exact => "exact",
+                    NumberMode::Fixed => "fixed",
+                }
+            );
+            println!("decimals: {}", config.decimals);
+        }
+        _ => println!("Unknown command ':{}'", command),
+    }
+}
+
 fn parse_arguments() -> Configuration {
     let config = App::new(env!("CARGO_PKG_NAME"))
         .version(env!("CARGO_PKG_VERSION"))
@@ -165,7 +305,7 @@ fn parse_arguments() -> Configuration {
                 .long("base")
                 .takes_value(true)
                 .value_name("RADIX")
-                .help("set the radix of calculation output (1 - 36)"),
+                .help("set the radix of calculation output (2 - 36)"),
         )
         .arg(
             Arg::with_name("INPUT")
@@ -178,36 +318,107 @@ fn parse_arguments() -> Configuration {
                 .long("radian")
                 .help("set eva to radian mode"),
         )
+        .arg(
+            Arg::with_name("numbers")
+                .long("numbers")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["float", "exact", "fixed"])
+                .help("select the internal number representation"),
+        )
+        .arg(
+            Arg::with_name("exact")
+                .long("exact")
+                .conflicts_with("numbers")
+                .help("shorthand for `--numbers exact`"),
+        )
+        .arg(
+            Arg::with_name("decimals")
+                .long("decimals")
+                .takes_value(true)
+                .value_name("DECIMALS")
+                .help("internal precision used by `--numbers fixed` (independent of --fix)"),
+        )
+        .arg(
+            Arg::with_name("history-length")
+                .long("history-length")
+                .takes_value(true)
+                .value_name("LENGTH")
+                .help("number of prior answers recallable via `_1`, `_2`, ... or `ans(n)`"),
+        )
         .get_matches();
 
     let mut input = String::new();
     if let Some(i) = config.value_of("INPUT") {
         input.push_str(i);
     };
+    let numbers = if config.is_present("exact") {
+        NumberMode::Exact
+    } else {
+        match config.value_of("numbers") {
+            Some("exact") => NumberMode::Exact,
+            Some("fixed") => NumberMode::Fixed,
+            _ => NumberMode::Float,
+        }
+    };
     Configuration {
         radian_mode: config.is_present("radian"),
         fix: config.value_of("fix").unwrap_or("10").parse().unwrap(),
         base: config.value_of("base").unwrap_or("10").parse().unwrap(),
+        numbers,
+        decimals: config.value_of("decimals").unwrap_or("10").parse().unwrap(),
+        history_length: config
+            .value_of("history-length")
+            .unwrap_or("100")
+            .parse()
+            .unwrap(),
         input,
     }
 }
 
-pub fn eval_math_expression(input: &str, prev_ans: Option<f64>) -> Result<f64, CalcError> {
+/// Lexes, shunts and evaluates `input`, returning the raw `Value` at full
+/// precision. Used by the REPL/command-mode print path, which renders it
+/// without the lossy f64 round-trip `eval_math_expression` below applies.
+pub fn eval_math_value(
+    input: &str,
+    history: &[f64],
+    symbols: &Symbols,
+    config: &Configuration,
+) -> Result<Value, CalcError> {
     let input = input.trim().replace(" ", "");
     if input == "help" {
         return Err(CalcError::Help);
     }
     if input.is_empty() {
-        return Ok(0.);
+        return Ok(Value::Inexact(0.));
     }
     let input = format::autobalance_parens(&input[..])?;
-    let lexed = lexer(&input[..], prev_ans)?;
+    let lexed = lexer(&input[..], history, symbols, config)?;
     let postfixed = to_postfix(lexed)?;
-    let evaled = eval_postfix(postfixed)?;
-    let evaled_fixed = format!("{:.*}", CONFIGURATION.fix, evaled)
-        .parse::<f64>()
-        .unwrap();
-    Ok(evaled_fixed)
+    eval_postfix(postfixed, symbols, config)
+}
+
+/// Rounds a `Value` to `config.fix` decimal places and collapses it to an
+/// `f64`, the representation history/recall (`_`, `ans(n)`) and variables
+/// store. This necessarily gives up exact/fixed-point's extra precision;
+/// `pprint` renders the un-rounded `Value` directly instead of going
+/// through this.
+fn round_to_f64(value: &Value, config: &Configuration) -> f64 {
+    match value {
+        Value::Exact(r) => r.to_fixed_string(config.fix).parse().unwrap(),
+        Value::Fixed(d) => d.to_display_string(config.fix).parse().unwrap(),
+        Value::Inexact(f) => format!("{:.*}", config.fix, f).parse().unwrap(),
+    }
+}
+
+pub fn eval_math_expression(
+    input: &str,
+    history: &[f64],
+    symbols: &Symbols,
+    config: &Configuration,
+) -> Result<f64, CalcError> {
+    let evaled = eval_math_value(input, history, symbols, config)?;
+    Ok(round_to_f64(&evaled, config))
 }
 
 #[cfg(test)]
@@ -216,101 +427,122 @@ mod tests {
 
     #[test]
     fn basic_ops() {
-        let evaled = eval_math_expression("6*2 + 3 + 12 -3", Some(0f64)).unwrap();
+        let evaled = eval_math_expression("6*2 + 3 + 12 -3", &[0.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(24., evaled);
     }
     #[test]
     fn trignometric_fns() {
-        let evaled = eval_math_expression("sin(30) + tan(45", Some(0f64)).unwrap();
+        let evaled = eval_math_expression("sin(30) + tan(45", &[0.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(1.5, evaled);
     }
     #[test]
     fn brackets() {
-        let evaled = eval_math_expression("(((1 + 2 + 3) ^ 2 ) - 4)", Some(0f64)).unwrap();
+        let evaled = eval_math_expression("(((1 + 2 + 3) ^ 2 ) - 4)", &[0.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(32., evaled);
     }
     #[test]
     fn exponentiation() {
-        let evaled = eval_math_expression("2 ** 2 ** 3", None).unwrap();
+        let evaled = eval_math_expression("2 ** 2 ** 3", &[], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(256., evaled); // 2^(2^3), not (2^2)^3
     }
     #[test]
     fn floating_ops() {
-        let evaled = eval_math_expression("1.2816 + 1 + 1.2816/1.2", Some(0f64)).unwrap();
+        let evaled = eval_math_expression("1.2816 + 1 + 1.2816/1.2", &[0.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(3.3496, evaled);
     }
     #[test]
     fn inverse_trignometric_fns() {
-        let evaled = eval_math_expression("deg(asin(1) + acos(1))", Some(0f64)).unwrap();
+        let evaled = eval_math_expression("deg(asin(1) + acos(1))", &[0.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(90., evaled);
     }
     #[test]
     fn sigmoid_fns() {
-        let evaled = eval_math_expression("1 / (1 + e^-7)", Some(0f64)).unwrap();
+        let evaled = eval_math_expression("1 / (1 + e^-7)", &[0.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(0.9990889488, evaled);
     }
     #[test]
     fn prev_ans() {
-        let evaled = eval_math_expression("_ + 9", Some(9f64)).unwrap();
+        let evaled = eval_math_expression("_ + 9", &[9.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(18.0, evaled);
     }
     #[test]
     fn eval_with_zero_prev() {
-        let evaled = eval_math_expression("9 + _ ", Some(0f64)).unwrap();
+        let evaled = eval_math_expression("9 + _ ", &[0.], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(9., evaled);
     }
     #[test]
+    fn eval_indexed_recall() {
+        let history = [10., 20., 30.];
+        let evaled = eval_math_expression("_2 + _3", &history, &Symbols::new(), &Configuration::default()).unwrap();
+        assert_eq!(50., evaled);
+    }
+    #[test]
+    fn eval_ans_call_recall() {
+        let history = [10., 20., 30.];
+        let evaled = eval_math_expression("ans(1) + ans(3)", &history, &Symbols::new(), &Configuration::default()).unwrap();
+        assert_eq!(40., evaled);
+    }
+    #[test]
+    fn eval_recall_out_of_range() {
+        assert!(
+            match eval_math_expression("_5", &[1., 2.], &Symbols::new(), &Configuration::default()) {
+                Err(CalcError::Math(_)) => true,
+                _ => false,
+            }
+        );
+    }
+    #[test]
     fn eval_const_multiplication() {
-        let evaled = eval_math_expression("e2", None).unwrap();
+        let evaled = eval_math_expression("e2", &[], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(5.4365636569, evaled);
     }
     #[test]
     fn eval_round() {
-        let evaled = eval_math_expression("round(0.5)+round(2.4)", None).unwrap();
+        let evaled = eval_math_expression("round(0.5)+round(2.4)", &[], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(3., evaled);
     }
     #[test]
     fn eval_exp2() {
         assert_eq!(
             256.,
-            eval_math_expression("exp2(8)", None).unwrap()
+            eval_math_expression("exp2(8)", &[], &Symbols::new(), &Configuration::default()).unwrap()
         );
     }
     #[test]
     fn eval_exp() {
         assert_eq!(
             20.0855369232 as f64,
-            eval_math_expression("exp(3)", None).unwrap()
+            eval_math_expression("exp(3)", &[], &Symbols::new(), &Configuration::default()).unwrap()
         );
     }
     #[test]
     fn eval_e_times_n() {
         assert_eq!(
             0. as f64,
-            eval_math_expression("e0", None).unwrap()
+            eval_math_expression("e0", &[], &Symbols::new(), &Configuration::default()).unwrap()
         );
     }
     #[test]
     fn eval_two_arg_fn() {
-        let evaled = eval_math_expression("nroot(27, 3)", None).unwrap();
+        let evaled = eval_math_expression("nroot(27, 3)", &[], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(3., evaled);
     }
     #[test]
     fn eval_log_n_base() {
-        let evaled = eval_math_expression("log(2^16, 4)", None).unwrap();
+        let evaled = eval_math_expression("log(2^16, 4)", &[], &Symbols::new(), &Configuration::default()).unwrap();
         assert_eq!(8., evaled);
     }
     #[test]
     fn eval_log_n_brackets() {
         assert_eq!(
             8.0000110068 as f64,
-            eval_math_expression("log(1+(2^16),4)", None).unwrap()
+            eval_math_expression("log(1+(2^16),4)", &[], &Symbols::new(), &Configuration::default()).unwrap()
         );
     }
     #[test]
     fn eval_mismatched_parens_in_multiarg_fn() {
         assert!(
-            match eval_math_expression("log(1+(2^16, 4)", None) {
+            match eval_math_expression("log(1+(2^16, 4)", &[], &Symbols::new(), &Configuration::default()) {
                 Err(CalcError::Syntax(_)) => true,
                 _ => false,
             }
@@ -319,7 +551,7 @@ mod tests {
     #[test]
     fn eval_comma_without_multiarg_fn() {
         assert!(
-            match eval_math_expression("1+(2^16, 4)", None) {
+            match eval_math_expression("1+(2^16, 4)", &[], &Symbols::new(), &Configuration::default()) {
                 Err(CalcError::Syntax(_)) => true,
                 _ => false,
             }
@@ -328,7 +560,7 @@ mod tests {
     #[test]
     fn eval_unexpected_comma() {
         assert!(
-            match eval_math_expression("(1+1,2+2)", None) {
+            match eval_math_expression("(1+1,2+2)", &[], &Symbols::new(), &Configuration::default()) {
                 Err(CalcError::Parser(y)) => {
                     assert_eq!("Too many operators, Too little operands", y);
                     true
@@ -341,13 +573,13 @@ mod tests {
     fn eval_nroot_expr_on_both_sides() {
         assert_eq!(
             1.1294396449 as f64,
-            eval_math_expression("nroot(2+2,4+e^2)", None).unwrap()
+            eval_math_expression("nroot(2+2,4+e^2)", &[], &Symbols::new(), &Configuration::default()).unwrap()
         );
     }
     #[test]
     fn eval_comma_left_paren_mixup() {
         assert!(
-            match eval_math_expression("exp 2,3)", None) {
+            match eval_math_expression("exp 2,3)", &[], &Symbols::new(), &Configuration::default()) {
                 Err(CalcError::Syntax(y)) => {
                     assert_eq!("Mismatched parentheses!", y);
                     true
@@ -356,7 +588,7 @@ mod tests {
             }
         );
         assert!(
-            match eval_math_expression("exp,2,3)", None) {
+            match eval_math_expression("exp,2,3)", &[], &Symbols::new(), &Configuration::default()) {
                 Err(CalcError::Syntax(y)) => {
                     assert_eq!("Mismatched parentheses!", y);
                     true
@@ -369,13 +601,13 @@ mod tests {
     fn eval_log10() {
         assert_eq!(
             3 as f64,
-            eval_math_expression("log10(1000)", None).unwrap()
+            eval_math_expression("log10(1000)", &[], &Symbols::new(), &Configuration::default()).unwrap()
         );
     }
     #[test]
     fn eval_mismatched_args() {
         assert!(
-            match eval_math_expression("nroot(23,3,4)", None) {
+            match eval_math_expression("nroot(23,3,4)", &[], &Symbols::new(), &Configuration::default()) {
                 Err(CalcError::Parser(y)) => {
                     assert_eq!("Too many operators, Too little operands", y);
                     true
@@ -384,7 +616,7 @@ mod tests {
             }
         );
         assert!(
-            match eval_math_expression("nroot(23)", None) {
+            match eval_math_expression("nroot(23)", &[], &Symbols::new(), &Configuration::default()) {
                 Err(CalcError::Parser(y)) => {
                     assert_eq!("Too few arguments (1) for function nroot (requires 2)!", y);
                     true
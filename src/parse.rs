@@ -0,0 +1,262 @@
+/*
+ * parse.rs
+ * Shunting-yard conversion to postfix, and postfix evaluation.
+ */
+
+use std::collections::HashMap;
+
+use crate::error::CalcError;
+use crate::lex::{arity, Token};
+use crate::rational::Value;
+use crate::symbols::Symbols;
+use crate::Configuration;
+
+/// An item on the operator stack used while shunting-yarding. `is_call`
+/// records whether an `(` was opened right after a function name, so commas
+/// outside of a function call (e.g. `(1, 2)`) can be rejected.
+enum StackItem {
+    Op(Token),
+    LParen { is_call: bool },
+}
+
+pub fn to_postfix(tokens: Vec<Token>) -> Result<Vec<Token>, CalcError> {
+    let mut output = Vec::new();
+    let mut stack: Vec<StackItem> = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::Num(_) | Token::Var(_) => output.push(token),
+            Token::Function(_) | Token::UserFunction(_) => stack.push(StackItem::Op(token)),
+            Token::Comma => {
+                loop {
+                    match stack.pop() {
+                        Some(StackItem::LParen { is_call: true }) => {
+                            stack.push(StackItem::LParen { is_call: true });
+                            break;
+                        }
+                        Some(StackItem::LParen { is_call: false }) => {
+                            if stack.is_empty() {
+                                // Comma inside a top-level, non-call paren, e.g.
+                                // `(1+1,2+2)`: there's no function to bind the
+                                // arguments to, so let both halves fall through
+                                // to postfix as-is; eval_postfix then reports the
+                                // leftover operand the usual way.
+                                stack.push(StackItem::LParen { is_call: false });
+                                break;
+                            }
+                            return Err(CalcError::Syntax("Mismatched parentheses!".to_string()));
+                        }
+                        None => {
+                            return Err(CalcError::Syntax("Mismatched parentheses!".to_string()));
+                        }
+                        Some(StackItem::Op(op)) => output.push(op),
+                    }
+                }
+            }
+            Token::LParen => {
+                let is_call = matches!(
+                    stack.last(),
+                    Some(StackItem::Op(Token::Function(_))) | Some(StackItem::Op(Token::UserFunction(_)))
+                );
+                stack.push(StackItem::LParen { is_call });
+            }
+            Token::RParen => {
+                loop {
+                    match stack.pop() {
+                        Some(StackItem::LParen { .. }) => break,
+                        Some(StackItem::Op(op)) => output.push(op),
+                        None => return Err(CalcError::Syntax("Mismatched parentheses!".to_string())),
+                    }
+                }
+                if matches!(
+                    stack.last(),
+                    Some(StackItem::Op(Token::Function(_))) | Some(StackItem::Op(Token::UserFunction(_)))
+                ) {
+                    if let Some(StackItem::Op(f)) = stack.pop() {
+                        output.push(f);
+                    }
+                }
+            }
+            _ => {
+                while let Some(StackItem::Op(top)) = stack.last() {
+                    if precedence(top) > precedence(&token)
+                        || (precedence(top) == precedence(&token) && !right_assoc(&token))
+                    {
+                        if let Some(StackItem::Op(op)) = stack.pop() {
+                            output.push(op);
+                        }
+                    } else {
+                        break;
+                    }
+                }
+                stack.push(StackItem::Op(token));
+            }
+        }
+    }
+
+    while let Some(item) = stack.pop() {
+        match item {
+            StackItem::Op(op) => output.push(op),
+            StackItem::LParen { .. } => {
+                return Err(CalcError::Syntax("Mismatched parentheses!".to_string()))
+            }
+        }
+    }
+    Ok(output)
+}
+
+fn precedence(token: &Token) -> u8 {
+    match token {
+        Token::Negate => 4,
+        Token::Caret => 3,
+        Token::Multiply | Token::Divide | Token::Modulo => 2,
+        Token::Plus | Token::Minus => 1,
+        _ => 0,
+    }
+}
+
+fn right_assoc(token: &Token) -> bool {
+    matches!(token, Token::Caret | Token::Negate)
+}
+
+pub fn eval_postfix(
+    tokens: Vec<Token>,
+    symbols: &Symbols,
+    config: &Configuration,
+) -> Result<Value, CalcError> {
+    eval_postfix_with_locals(tokens, symbols, &HashMap::new(), config)
+}
+
+/// `locals` binds a user function's parameter names to their argument values
+/// for the duration of one call; empty at the top level.
+fn eval_postfix_with_locals(
+    tokens: Vec<Token>,
+    symbols: &Symbols,
+    locals: &HashMap<String, Value>,
+    config: &Configuration,
+) -> Result<Value, CalcError> {
+    let mut stack: Vec<Value> = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::Num(v) => stack.push(v),
+            Token::Var(name) => {
+                if let Some(v) = locals.get(&name) {
+                    stack.push(v.clone());
+                } else if let Some(v) = symbols.vars.get(&name) {
+                    stack.push(Value::Inexact(*v));
+                } else {
+                    return Err(CalcError::Math(format!("Unknown variable '{}'", name)));
+                }
+            }
+            Token::Function(name) => {
+                let n = arity(&name);
+                if stack.len() < n {
+                    return Err(CalcError::Parser(format!(
+                        "Too few arguments ({}) for function {} (requires {})!",
+                        stack.len(),
+                        name,
+                        n
+                    )));
+                }
+                let result = if n == 2 {
+                    let b = stack.pop().unwrap();
+                    let a = stack.pop().unwrap();
+                    apply_2_arg(&name, a, b)?
+                } else {
+                    let a = stack.pop().unwrap();
+                    apply_1_arg(&name, a, config)?
+                };
+                stack.push(result);
+            }
+            Token::UserFunction(name) => {
+                let func = symbols
+                    .funcs
+                    .get(&name)
+                    .ok_or_else(|| CalcError::Parser(format!("Unknown function {}", name)))?;
+                let n = func.params.len();
+                if stack.len() < n {
+                    return Err(CalcError::Parser(format!(
+                        "Too few arguments ({}) for function {} (requires {})!",
+                        stack.len(),
+                        name,
+                        n
+                    )));
+                }
+                let mut args: Vec<Value> = (0..n).map(|_| stack.pop().unwrap()).collect();
+                args.reverse();
+                let mut call_locals = locals.clone();
+                for (param, arg) in func.params.iter().zip(args) {
+                    call_locals.insert(param.clone(), arg);
+                }
+                let result =
+                    eval_postfix_with_locals(func.body.clone(), symbols, &call_locals, config)?;
+                stack.push(result);
+            }
+            Token::Negate => {
+                let a = stack.pop().ok_or_else(too_few_operands)?;
+                stack.push(a.neg());
+            }
+            op => {
+                if stack.len() < 2 {
+                    return Err(too_few_operands());
+                }
+                let b = stack.pop().unwrap();
+                let a = stack.pop().unwrap();
+                let result = match op {
+                    Token::Plus => a.add(&b),
+                    Token::Minus => a.sub(&b),
+                    Token::Multiply => a.mul(&b),
+                    Token::Divide => a.div(&b)?,
+                    Token::Modulo => Value::Inexact(a.to_f64() % b.to_f64()),
+                    Token::Caret => a.pow(&b)?,
+                    _ => unreachable!(),
+                };
+                stack.push(result);
+            }
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err(too_few_operands());
+    }
+    Ok(stack.pop().unwrap())
+}
+
+fn too_few_operands() -> CalcError {
+    CalcError::Parser("Too many operators, Too little operands".to_string())
+}
+
+fn apply_1_arg(name: &str, a: Value, config: &Configuration) -> Result<Value, CalcError> {
+    let x = a.to_f64();
+    let radian = config.radian_mode;
+    let to_rad = |v: f64| if radian { v } else { v.to_radians() };
+    let value = match name {
+        "sin" => to_rad(x).sin(),
+        "cos" => to_rad(x).cos(),
+        "tan" => to_rad(x).tan(),
+        "asin" => x.asin(),
+        "acos" => x.acos(),
+        "atan" => x.atan(),
+        "deg" => x.to_degrees(),
+        "rad" => x.to_radians(),
+        "round" => x.round(),
+        "exp" => x.exp(),
+        "exp2" => x.exp2(),
+        "log10" => x.log10(),
+        "sqrt" => x.sqrt(),
+        _ => return Err(CalcError::Parser(format!("Unknown function {}", name))),
+    };
+    Ok(Value::Inexact(value))
+}
+
+fn apply_2_arg(name: &str, a: Value, b: Value) -> Result<Value, CalcError> {
+    let x = a.to_f64();
+    let y = b.to_f64();
+    let value = match name {
+        "log" => x.log(y),
+        "nroot" => x.powf(1. / y),
+        _ => return Err(CalcError::Parser(format!("Unknown function {}", name))),
+    };
+    Ok(Value::Inexact(value))
+}
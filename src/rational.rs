@@ -0,0 +1,230 @@
+/*
+ * rational.rs
+ * Exact rational arithmetic backed by arbitrary-precision integers, used by
+ * `--exact` mode so results like `0.1 + 0.2` stay precise instead of picking
+ * up f64 rounding error.
+ */
+
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+
+use crate::error::CalcError;
+use crate::fixed::FixedPoint;
+
+/// A reduced fraction `num / den` with `den > 0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rational {
+    pub num: BigInt,
+    pub den: BigInt,
+}
+
+impl Rational {
+    pub fn new(num: BigInt, den: BigInt) -> Rational {
+        let mut r = Rational { num, den };
+        r.reduce();
+        r
+    }
+
+    pub fn from_i64(n: i64) -> Rational {
+        Rational::new(BigInt::from(n), BigInt::one())
+    }
+
+    /// Parses a literal like "1234" or "12.34" into an exact fraction.
+    pub fn from_literal(text: &str) -> Rational {
+        match text.find('.') {
+            None => Rational::from_i64(text.parse().unwrap_or(0)),
+            Some(dot) => {
+                let frac_digits = text.len() - dot - 1;
+                let digits: String = text.chars().filter(|c| *c != '.').collect();
+                let num: BigInt = digits.parse().unwrap_or_else(|_| BigInt::zero());
+                let den = BigInt::from(10).pow(frac_digits as u32);
+                Rational::new(num, den)
+            }
+        }
+    }
+
+    fn reduce(&mut self) {
+        if self.den.is_zero() {
+            return;
+        }
+        if self.den.is_negative() {
+            self.num = -self.num.clone();
+            self.den = -self.den.clone();
+        }
+        let g = gcd(self.num.clone(), self.den.clone());
+        if !g.is_zero() && g != BigInt::one() {
+            self.num /= &g;
+            self.den /= &g;
+        }
+    }
+
+    pub fn add(&self, other: &Rational) -> Rational {
+        Rational::new(
+            &self.num * &other.den + &other.num * &self.den,
+            &self.den * &other.den,
+        )
+    }
+
+    pub fn sub(&self, other: &Rational) -> Rational {
+        Rational::new(
+            &self.num * &other.den - &other.num * &self.den,
+            &self.den * &other.den,
+        )
+    }
+
+    pub fn mul(&self, other: &Rational) -> Rational {
+        Rational::new(&self.num * &other.num, &self.den * &other.den)
+    }
+
+    pub fn neg(&self) -> Rational {
+        Rational::new(-self.num.clone(), self.den.clone())
+    }
+
+    pub fn div(&self, other: &Rational) -> Result<Rational, CalcError> {
+        if other.num.is_zero() {
+            return Err(CalcError::Div0);
+        }
+        Ok(Rational::new(&self.num * &other.den, &self.den * &other.num))
+    }
+
+    /// Integer exponentiation; a negative exponent swaps num/den before raising.
+    pub fn pow(&self, exp: i64) -> Result<Rational, CalcError> {
+        if exp >= 0 {
+            Ok(Rational::new(
+                self.num.pow(exp as u32),
+                self.den.pow(exp as u32),
+            ))
+        } else {
+            if self.num.is_zero() {
+                return Err(CalcError::Div0);
+            }
+            let e = (-exp) as u32;
+            Ok(Rational::new(self.den.pow(e), self.num.pow(e)))
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        // BigInt doesn't cast to f64 directly; go through its decimal string.
+        let num: f64 = self.num.to_string().parse().unwrap_or(f64::NAN);
+        let den: f64 = self.den.to_string().parse().unwrap_or(f64::NAN);
+        num / den
+    }
+
+    /// Renders the fraction with `fix` decimal places, rounding half-up.
+    pub fn to_fixed_string(&self, fix: usize) -> String {
+        let scale = BigInt::from(10).pow(fix as u32);
+        let scaled = &self.num * &scale;
+        let half_den = &self.den / BigInt::from(2);
+        let rounded = if scaled.is_negative() {
+            (scaled - half_den) / &self.den
+        } else {
+            (scaled + half_den) / &self.den
+        };
+        let sign = if rounded.is_negative() { "-" } else { "" };
+        let digits = rounded.abs().to_string();
+        if fix == 0 {
+            return format!("{}{}", sign, digits);
+        }
+        let digits = if digits.len() <= fix {
+            "0".repeat(fix - digits.len() + 1) + &digits
+        } else {
+            digits
+        };
+        let (int_part, frac_part) = digits.split_at(digits.len() - fix);
+        format!("{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+fn gcd(a: BigInt, b: BigInt) -> BigInt {
+    if b.is_zero() {
+        a.abs()
+    } else {
+        gcd(b.clone(), a % b)
+    }
+}
+
+/// A value flowing through the eval pipeline: an exact fraction (`--exact`),
+/// a scaled fixed-point integer (`--numbers fixed`), or a plain f64 that has
+/// already lost precision (e.g. through a transcendental function).
+#[derive(Debug, Clone)]
+pub enum Value {
+    Exact(Rational),
+    Fixed(FixedPoint),
+    Inexact(f64),
+}
+
+impl Value {
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Value::Exact(r) => r.to_f64(),
+            Value::Fixed(d) => d.to_f64(),
+            Value::Inexact(f) => *f,
+        }
+    }
+
+    /// Marks this value as inexact, e.g. after it passes through sin/log/sqrt.
+    pub fn inexact(self) -> Value {
+        Value::Inexact(self.to_f64())
+    }
+
+    pub fn add(&self, other: &Value) -> Value {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) => Value::Exact(a.add(b)),
+            (Value::Fixed(a), Value::Fixed(b)) => Value::Fixed(a.add(b)),
+            _ => Value::Inexact(self.to_f64() + other.to_f64()),
+        }
+    }
+
+    pub fn sub(&self, other: &Value) -> Value {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) => Value::Exact(a.sub(b)),
+            (Value::Fixed(a), Value::Fixed(b)) => Value::Fixed(a.sub(b)),
+            _ => Value::Inexact(self.to_f64() - other.to_f64()),
+        }
+    }
+
+    pub fn mul(&self, other: &Value) -> Value {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) => Value::Exact(a.mul(b)),
+            (Value::Fixed(a), Value::Fixed(b)) => Value::Fixed(a.mul(b)),
+            _ => Value::Inexact(self.to_f64() * other.to_f64()),
+        }
+    }
+
+    pub fn neg(&self) -> Value {
+        match self {
+            Value::Exact(a) => Value::Exact(a.neg()),
+            Value::Fixed(a) => Value::Fixed(a.neg()),
+            Value::Inexact(a) => Value::Inexact(-a),
+        }
+    }
+
+    pub fn div(&self, other: &Value) -> Result<Value, CalcError> {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) => Ok(Value::Exact(a.div(b)?)),
+            (Value::Fixed(a), Value::Fixed(b)) => Ok(Value::Fixed(a.div(b)?)),
+            _ => {
+                let b = other.to_f64();
+                if b == 0. {
+                    return Err(CalcError::Div0);
+                }
+                Ok(Value::Inexact(self.to_f64() / b))
+            }
+        }
+    }
+
+    /// Integer `^`; falls back to float exponentiation for non-integer exponents.
+    pub fn pow(&self, other: &Value) -> Result<Value, CalcError> {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) if b.den == BigInt::one() => {
+                let exp: i64 = b.num.to_string().parse().unwrap_or(0);
+                Ok(Value::Exact(a.pow(exp)?))
+            }
+            (Value::Fixed(a), Value::Fixed(b)) if b.is_integer() => {
+                let exp: i64 = b.to_f64() as i64;
+                Ok(Value::Fixed(a.pow(exp)?))
+            }
+            _ => Ok(Value::Inexact(self.to_f64().powf(other.to_f64()))),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+/*
+ * readline.rs
+ * Sets up the rustyline editor used by the REPL.
+ */
+
+use rustyline::Editor;
+
+pub fn create_readline() -> Editor<()> {
+    Editor::<()>::new()
+}
@@ -0,0 +1,111 @@
+/*
+ * symbols.rs
+ * The REPL's variable and function bindings (`x = 3 + 4`, `f(a, b) = a^2 + b`),
+ * plus their persistence alongside history.txt so they survive across
+ * sessions.
+ */
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::CalcError;
+use crate::lex::{lexer, Token};
+use crate::parse::to_postfix;
+use crate::Configuration;
+
+/// A user-defined function: its parameter names, and its body already lexed
+/// and shunted to postfix (with `Token::Var(param)` standing in for each
+/// parameter), ready to hand straight to `eval_postfix_with_locals`. A
+/// parameter named like a built-in function or constant (`sin`, `e`, `pi`)
+/// lexes as that built-in instead.
+#[derive(Clone)]
+pub struct UserFunction {
+    pub params: Vec<String>,
+    pub body: Vec<Token>,
+    pub body_text: String,
+}
+
+#[derive(Default)]
+pub struct Symbols {
+    pub vars: HashMap<String, f64>,
+    pub funcs: HashMap<String, UserFunction>,
+}
+
+impl Symbols {
+    pub fn new() -> Symbols {
+        Symbols::default()
+    }
+
+    pub fn define_function(
+        &mut self,
+        name: &str,
+        params: Vec<String>,
+        body_text: &str,
+        config: &Configuration,
+    ) -> Result<(), CalcError> {
+        // Lex against the globals minus any that share a parameter's name,
+        // so each parameter lexes as `Token::Var`.
+        let mut scope = Symbols {
+            vars: self.vars.clone(),
+            funcs: self.funcs.clone(),
+        };
+        for p in &params {
+            scope.vars.remove(p);
+            scope.funcs.remove(p);
+        }
+        let body = to_postfix(lexer(body_text, &[], &scope, config)?)?;
+        self.funcs.insert(
+            name.to_string(),
+            UserFunction {
+                params,
+                body,
+                body_text: body_text.to_string(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn load(path: &Path, config: &Configuration) -> Symbols {
+        let mut symbols = Symbols::new();
+        let contents = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return symbols,
+        };
+        for line in contents.lines() {
+            let mut parts = line.splitn(3, '\t');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("var"), Some(name), Some(value)) => {
+                    if let Ok(v) = value.parse() {
+                        symbols.vars.insert(name.to_string(), v);
+                    }
+                }
+                (Some("fn"), Some(name), Some(rest)) => {
+                    if let Some((params, body_text)) = rest.split_once('\t') {
+                        let params: Vec<String> =
+                            params.split(',').filter(|s| !s.is_empty()).map(String::from).collect();
+                        let _ = symbols.define_function(name, params, body_text, config);
+                    }
+                }
+                _ => {}
+            }
+        }
+        symbols
+    }
+
+    pub fn save(&self, path: &Path) {
+        let mut out = String::new();
+        for (name, value) in &self.vars {
+            out.push_str(&format!("var\t{}\t{}\n", name, value));
+        }
+        for (name, func) in &self.funcs {
+            out.push_str(&format!(
+                "fn\t{}\t{}\t{}\n",
+                name,
+                func.params.join(","),
+                func.body_text
+            ));
+        }
+        let _ = fs::write(path, out);
+    }
+}